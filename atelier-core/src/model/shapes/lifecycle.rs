@@ -0,0 +1,557 @@
+use crate::model::shapes::{Operation, Resource};
+use crate::model::{Identifier, ShapeID};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The lifecycle slot a `Resource` binds an operation to; used to detect a `ShapeID` reused
+/// across more than one slot. A [`LifecycleViolation`] does not record the slot.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LifecycleSlot {
+    /// The resource's `create` lifecycle operation.
+    Create,
+    /// The resource's `put` lifecycle operation.
+    Put,
+    /// The resource's `read` lifecycle operation.
+    Read,
+    /// The resource's `update` lifecycle operation.
+    Update,
+    /// The resource's `delete` lifecycle operation.
+    Delete,
+    /// The resource's `list` lifecycle operation.
+    List,
+    /// One of the resource's instance `operations`.
+    Instance,
+    /// One of the resource's `collection_operations`.
+    Collection,
+}
+
+///
+/// The specific semantic rule a [`LifecycleViolation`] reports.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LifecycleRule {
+    /// The bound shape id does not resolve to an `Operation` shape.
+    UnresolvedOperation,
+    /// The operation's input does not bind a member for the named resource identifier.
+    MissingIdentifierBinding(Identifier),
+    /// `read` and `list` must be bound to a `@readonly` operation.
+    NotReadOnly,
+    /// `put`, `update`, and `delete` must be bound to an `@idempotent` operation.
+    NotIdempotent,
+    /// A non-idempotent `create` must produce the named resource identifier as output.
+    CreateMissingIdentifierOutput(Identifier),
+    /// The shape is bound to more than one lifecycle slot across the resource tree.
+    DuplicateLifecycleBinding,
+}
+
+///
+/// A single semantic rule violation discovered while validating a resource's lifecycle
+/// operations, reported rather than panicking so a validator action can collect every issue in
+/// one pass.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifecycleViolation {
+    /// The resource the violation was found on.
+    pub resource: ShapeID,
+    /// The operation the violation relates to, if the rule is operation-specific.
+    pub operation: Option<ShapeID>,
+    /// The rule that was violated.
+    pub rule: LifecycleRule,
+}
+
+///
+/// Resolves the facts about operations and resources that `validate_resource_lifecycle` needs
+/// but that a bare `Resource`/`Operation` pair can't answer on their own: looking up the shape
+/// an identifier refers to, whether an operation carries the `@readonly`/`@idempotent` traits,
+/// and whether an operation's input or output structure binds a given identifier member.
+///
+pub trait LifecycleContext {
+    /// Look up the `Operation` shape bound to the given shape id.
+    fn operation(&self, id: &ShapeID) -> Option<&Operation>;
+
+    /// Look up the `Resource` shape bound to the given shape id.
+    fn resource(&self, id: &ShapeID) -> Option<&Resource>;
+
+    /// Does the given operation carry the `@readonly` trait?
+    fn is_read_only(&self, operation: &ShapeID) -> bool;
+
+    /// Does the given operation carry the `@idempotent` trait?
+    fn is_idempotent(&self, operation: &ShapeID) -> bool;
+
+    /// Does the given operation's input structure have a member named `name` bound to `shape`
+    /// (directly, or via an explicit identifier-binding trait)?
+    fn operation_binds_identifier(
+        &self,
+        operation: &ShapeID,
+        name: &Identifier,
+        shape: &ShapeID,
+    ) -> bool;
+
+    /// Does the given operation's output structure have a member named `name` bound to `shape`?
+    fn operation_produces_identifier(
+        &self,
+        operation: &ShapeID,
+        name: &Identifier,
+        shape: &ShapeID,
+    ) -> bool;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Validate the lifecycle operations of a `Resource` and all of its nested `resources()`
+/// against the Smithy semantic rules: every lifecycle operation, and every entry in
+/// `operations()`/`collection_operations()`, must bind all of the resource's `identifiers()`;
+/// `read`/`list` must be read-only; `put`/`update`/`delete` must be idempotent; a non-idempotent
+/// `create` must produce the resource's identifiers as output; and no `ShapeID` may be bound to
+/// more than one lifecycle slot across the resource tree.
+///
+/// Returns every violation found; an empty vector means the resource tree is valid.
+///
+pub fn validate_resource_lifecycle(
+    resource_id: &ShapeID,
+    resource: &Resource,
+    context: &dyn LifecycleContext,
+) -> Vec<LifecycleViolation> {
+    let mut violations = Vec::new();
+    let mut bound: HashMap<ShapeID, LifecycleSlot> = HashMap::new();
+    check_resource(resource_id, resource, context, &mut bound, &mut violations);
+    violations
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn check_resource(
+    resource_id: &ShapeID,
+    resource: &Resource,
+    context: &dyn LifecycleContext,
+    bound: &mut HashMap<ShapeID, LifecycleSlot>,
+    violations: &mut Vec<LifecycleViolation>,
+) {
+    for (slot, operation_id) in [
+        (LifecycleSlot::Create, resource.create()),
+        (LifecycleSlot::Put, resource.put()),
+        (LifecycleSlot::Read, resource.read()),
+        (LifecycleSlot::Update, resource.update()),
+        (LifecycleSlot::Delete, resource.delete()),
+        (LifecycleSlot::List, resource.list()),
+    ] {
+        if let Some(operation_id) = operation_id {
+            if !check_operation_resolves(resource_id, operation_id, context, violations) {
+                continue;
+            }
+            // The duplicate-binding rule is scoped to the six named lifecycle slots: Smithy
+            // doesn't forbid the same operation being referenced as a plain `operations()`/
+            // `collection_operations()` entry by more than one resource.
+            check_duplicate_binding(resource_id, operation_id, slot, bound, violations);
+            check_identifier_bindings(resource_id, resource, operation_id, context, violations);
+            check_lifecycle_specifics(
+                resource_id,
+                resource,
+                slot,
+                operation_id,
+                context,
+                violations,
+            );
+        }
+    }
+
+    for operation_id in resource.operations() {
+        if !check_operation_resolves(resource_id, operation_id, context, violations) {
+            continue;
+        }
+        check_identifier_bindings(resource_id, resource, operation_id, context, violations);
+    }
+
+    for operation_id in resource.collection_operations() {
+        if !check_operation_resolves(resource_id, operation_id, context, violations) {
+            continue;
+        }
+        check_identifier_bindings(resource_id, resource, operation_id, context, violations);
+    }
+
+    for child_id in resource.resources() {
+        if let Some(child) = context.resource(child_id) {
+            check_resource(child_id, child, context, bound, violations);
+        }
+    }
+}
+
+fn check_operation_resolves(
+    resource_id: &ShapeID,
+    operation_id: &ShapeID,
+    context: &dyn LifecycleContext,
+    violations: &mut Vec<LifecycleViolation>,
+) -> bool {
+    if context.operation(operation_id).is_some() {
+        true
+    } else {
+        violations.push(LifecycleViolation {
+            resource: resource_id.clone(),
+            operation: Some(operation_id.clone()),
+            rule: LifecycleRule::UnresolvedOperation,
+        });
+        false
+    }
+}
+
+fn check_duplicate_binding(
+    resource_id: &ShapeID,
+    operation_id: &ShapeID,
+    slot: LifecycleSlot,
+    bound: &mut HashMap<ShapeID, LifecycleSlot>,
+    violations: &mut Vec<LifecycleViolation>,
+) {
+    if bound.contains_key(operation_id) {
+        violations.push(LifecycleViolation {
+            resource: resource_id.clone(),
+            operation: Some(operation_id.clone()),
+            rule: LifecycleRule::DuplicateLifecycleBinding,
+        });
+    } else {
+        let _ = bound.insert(operation_id.clone(), slot);
+    }
+}
+
+fn check_identifier_bindings(
+    resource_id: &ShapeID,
+    resource: &Resource,
+    operation_id: &ShapeID,
+    context: &dyn LifecycleContext,
+    violations: &mut Vec<LifecycleViolation>,
+) {
+    for (name, shape) in resource.identifiers() {
+        if !context.operation_binds_identifier(operation_id, name, shape) {
+            violations.push(LifecycleViolation {
+                resource: resource_id.clone(),
+                operation: Some(operation_id.clone()),
+                rule: LifecycleRule::MissingIdentifierBinding(name.clone()),
+            });
+        }
+    }
+}
+
+fn check_lifecycle_specifics(
+    resource_id: &ShapeID,
+    resource: &Resource,
+    slot: LifecycleSlot,
+    operation_id: &ShapeID,
+    context: &dyn LifecycleContext,
+    violations: &mut Vec<LifecycleViolation>,
+) {
+    match slot {
+        LifecycleSlot::Read | LifecycleSlot::List => {
+            if !context.is_read_only(operation_id) {
+                violations.push(LifecycleViolation {
+                    resource: resource_id.clone(),
+                    operation: Some(operation_id.clone()),
+                    rule: LifecycleRule::NotReadOnly,
+                });
+            }
+        }
+        LifecycleSlot::Put | LifecycleSlot::Update | LifecycleSlot::Delete => {
+            if !context.is_idempotent(operation_id) {
+                violations.push(LifecycleViolation {
+                    resource: resource_id.clone(),
+                    operation: Some(operation_id.clone()),
+                    rule: LifecycleRule::NotIdempotent,
+                });
+            }
+        }
+        LifecycleSlot::Create => {
+            if !context.is_idempotent(operation_id) {
+                for (name, shape) in resource.identifiers() {
+                    if !context.operation_produces_identifier(operation_id, name, shape) {
+                        violations.push(LifecycleViolation {
+                            resource: resource_id.clone(),
+                            operation: Some(operation_id.clone()),
+                            rule: LifecycleRule::CreateMissingIdentifierOutput(name.clone()),
+                        });
+                    }
+                }
+            }
+        }
+        LifecycleSlot::Instance | LifecycleSlot::Collection => {}
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    #[derive(Default)]
+    struct TestContext {
+        operations: HashMap<ShapeID, Operation>,
+        resources: HashMap<ShapeID, Resource>,
+        read_only: HashSet<ShapeID>,
+        idempotent: HashSet<ShapeID>,
+        input_bindings: Vec<(ShapeID, Identifier, ShapeID)>,
+        output_bindings: Vec<(ShapeID, Identifier, ShapeID)>,
+    }
+
+    impl LifecycleContext for TestContext {
+        fn operation(&self, id: &ShapeID) -> Option<&Operation> {
+            self.operations.get(id)
+        }
+
+        fn resource(&self, id: &ShapeID) -> Option<&Resource> {
+            self.resources.get(id)
+        }
+
+        fn is_read_only(&self, operation: &ShapeID) -> bool {
+            self.read_only.contains(operation)
+        }
+
+        fn is_idempotent(&self, operation: &ShapeID) -> bool {
+            self.idempotent.contains(operation)
+        }
+
+        fn operation_binds_identifier(
+            &self,
+            operation: &ShapeID,
+            name: &Identifier,
+            shape: &ShapeID,
+        ) -> bool {
+            self.input_bindings
+                .iter()
+                .any(|(o, n, s)| o == operation && n == name && s == shape)
+        }
+
+        fn operation_produces_identifier(
+            &self,
+            operation: &ShapeID,
+            name: &Identifier,
+            shape: &ShapeID,
+        ) -> bool {
+            self.output_bindings
+                .iter()
+                .any(|(o, n, s)| o == operation && n == name && s == shape)
+        }
+    }
+
+    fn sid(s: &str) -> ShapeID {
+        ShapeID::from_str(s).unwrap()
+    }
+
+    fn iid(s: &str) -> Identifier {
+        Identifier::from_str(s).unwrap()
+    }
+
+    fn widget_resource() -> (ShapeID, Resource) {
+        let resource_id = sid("smithy.example#Widget");
+        let mut resource = Resource::default();
+        resource.add_identifier(iid("id"), sid("smithy.example#WidgetId"));
+        (resource_id, resource)
+    }
+
+    #[test]
+    fn missing_identifier_binding_is_reported() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_read(sid("smithy.example#GetWidget"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#GetWidget"), Operation::default());
+        context.read_only.insert(sid("smithy.example#GetWidget"));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#GetWidget")),
+                rule: LifecycleRule::MissingIdentifierBinding(iid("id")),
+            }]
+        );
+    }
+
+    #[test]
+    fn read_must_be_read_only() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_read(sid("smithy.example#GetWidget"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#GetWidget"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#GetWidget"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#GetWidget")),
+                rule: LifecycleRule::NotReadOnly,
+            }]
+        );
+    }
+
+    #[test]
+    fn put_must_be_idempotent() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_put(sid("smithy.example#PutWidget"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#PutWidget"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#PutWidget"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#PutWidget")),
+                rule: LifecycleRule::NotIdempotent,
+            }]
+        );
+    }
+
+    #[test]
+    fn non_idempotent_create_must_produce_identifiers() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_create(sid("smithy.example#CreateWidget"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#CreateWidget"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#CreateWidget"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#CreateWidget")),
+                rule: LifecycleRule::CreateMissingIdentifierOutput(iid("id")),
+            }]
+        );
+    }
+
+    #[test]
+    fn idempotent_create_is_exempt_from_identifier_output() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_create(sid("smithy.example#CreateWidget"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#CreateWidget"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#CreateWidget"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+        context
+            .idempotent
+            .insert(sid("smithy.example#CreateWidget"));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn duplicate_lifecycle_binding_is_reported() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_read(sid("smithy.example#SharedOp"));
+        resource.set_list(sid("smithy.example#SharedOp"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#SharedOp"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#SharedOp"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+        context.read_only.insert(sid("smithy.example#SharedOp"));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#SharedOp")),
+                rule: LifecycleRule::DuplicateLifecycleBinding,
+            }]
+        );
+    }
+
+    #[test]
+    fn unresolved_operation_short_circuits_other_checks() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.set_read(sid("smithy.example#NotAnOperation"));
+
+        let context = TestContext::default();
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert_eq!(
+            violations,
+            vec![LifecycleViolation {
+                resource: resource_id,
+                operation: Some(sid("smithy.example#NotAnOperation")),
+                rule: LifecycleRule::UnresolvedOperation,
+            }]
+        );
+    }
+
+    #[test]
+    fn shared_instance_and_collection_operation_is_not_flagged_as_duplicate() {
+        let (resource_id, mut resource) = widget_resource();
+        resource.add_operation(sid("smithy.example#SharedOp"));
+        resource.add_collection_operation(sid("smithy.example#SharedOp"));
+
+        let mut context = TestContext::default();
+        context
+            .operations
+            .insert(sid("smithy.example#SharedOp"), Operation::default());
+        context.input_bindings.push((
+            sid("smithy.example#SharedOp"),
+            iid("id"),
+            sid("smithy.example#WidgetId"),
+        ));
+
+        let violations = validate_resource_lifecycle(&resource_id, &resource, &context);
+
+        assert!(violations.is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::error::invalid_value_variant;
-use crate::model::shapes::{Member, Valued};
+use crate::model::shapes::{Member, ShapeKind, Valued};
 use crate::model::values::{Key, NodeValue};
 use crate::model::{Identifier, ShapeID};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 // ------------------------------------------------------------------------------------------------
@@ -16,6 +17,22 @@ pub struct Service {
     version: Member,    // **required** Value::String
     operations: Member, // Value::Array(Value::ShapeID)
     resources: Member,  // Value::Array(Value::ShapeID)
+    errors: Member,     // Value::Array(Value::ShapeID)
+    rename: Member,     // Value::Object(ShapeID, Value::String)
+}
+
+///
+/// Describes why a shape was included in a [`Service`]'s transitive closure, as computed by
+/// `Service::closure`.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClosureReason {
+    /// The shape is bound directly to the service, one of its operations, or one of its
+    /// resources (e.g. an operation's `input`, or a resource's `identifiers`).
+    Bound,
+    /// The shape is only reachable by following a reference from another shape already in the
+    /// closure (e.g. a resource nested under another resource).
+    Referenced,
 }
 
 ///
@@ -80,7 +97,7 @@ macro_rules! optional_member {
 
 #[doc(hidden)]
 macro_rules! array_member {
-    ($collection:ident, $member:ident, $has_fn:ident, $add_fn:ident, $append_fn:ident, $remove_fn:ident) => {
+    ($collection:ident, $member:ident, $has_fn:ident, $add_fn:ident, $append_fn:ident, $remove_fn:ident, $retain_fn:ident, $clear_fn:ident) => {
         /// Returns `true` if this member's collection has _any_ elements, else `false`.
         pub fn $has_fn(&self) -> bool {
             match self.$collection.value() {
@@ -137,19 +154,45 @@ macro_rules! array_member {
             }
         }
 
-        /// Remove an element, with the given identifier, to this member's collection.
+        /// Remove every element equal to the given identifier from this member's collection.
         pub fn $remove_fn(&mut self, $member: &ShapeID) {
             match self.$collection.value_mut() {
                 Some(v) => match v {
                     NodeValue::Array(vs) => {
                         let id_value = NodeValue::ShapeID($member.clone());
-                        vs.retain(|v| v == &id_value);
+                        vs.retain(|v| v != &id_value);
                     }
                     _ => invalid_value_variant("Array"),
                 },
                 _ => invalid_value_variant("Array"),
             }
         }
+
+        /// Retain only the elements of this member's collection for which `predicate` returns
+        /// `true`.
+        pub fn $retain_fn(&mut self, mut predicate: impl FnMut(&ShapeID) -> bool) {
+            match self.$collection.value_mut() {
+                Some(v) => match v {
+                    NodeValue::Array(vs) => vs.retain(|v| match v {
+                        NodeValue::ShapeID(id) => predicate(id),
+                        _ => invalid_value_variant("ShapeID"),
+                    }),
+                    _ => invalid_value_variant("Array"),
+                },
+                _ => invalid_value_variant("Array"),
+            }
+        }
+
+        /// Remove every element from this member's collection.
+        pub fn $clear_fn(&mut self) {
+            match self.$collection.value_mut() {
+                Some(v) => match v {
+                    NodeValue::Array(vs) => vs.clear(),
+                    _ => invalid_value_variant("Array"),
+                },
+                _ => invalid_value_variant("Array"),
+            }
+        }
     };
 }
 
@@ -169,6 +212,14 @@ impl Default for Service {
                 Identifier::from_str("resources").unwrap(),
                 NodeValue::Array(Default::default()),
             ),
+            errors: Member::with_value(
+                Identifier::from_str("errors").unwrap(),
+                NodeValue::Array(Default::default()),
+            ),
+            rename: Member::with_value(
+                Identifier::from_str("rename").unwrap(),
+                NodeValue::Object(Default::default()),
+            ),
         }
     }
 }
@@ -185,9 +236,135 @@ impl Service {
             .set_value(NodeValue::String(version.to_string()))
     }
 
-    array_member! { operations, operation, has_operations, add_operation, append_operations, remove_operation }
+    array_member! { operations, operation, has_operations, add_operation, append_operations, remove_operation, retain_operations, clear_operations }
+
+    array_member! { resources, resource, has_resources, add_resource, append_resources, remove_resource, retain_resources, clear_resources }
+
+    array_member! { errors, error, has_errors, add_error, append_errors, remove_error, retain_errors, clear_errors }
+
+    /// Returns `true` if this service has any shape renames, else `false`.
+    pub fn has_renames(&self) -> bool {
+        match self.rename.value() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => !vs.is_empty(),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Return an iterator over the shape renames declared by this service, mapping each
+    /// fully-qualified `ShapeID` to the simple `Identifier` it is renamed to.
+    pub fn rename(&self) -> impl Iterator<Item = (&ShapeID, Identifier)> {
+        match self.rename.value() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => vs.iter().map(|(k, v)| {
+                    let to = match v {
+                        NodeValue::String(s) => Identifier::from_str(s).unwrap(),
+                        _ => invalid_value_variant("String"),
+                    };
+                    (k.as_shape_id().unwrap(), to)
+                }),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Add a rename entry, indicating that `from` should be renamed to the simple name `to`
+    /// wherever it would otherwise collide with another shape in a generated client.
+    pub fn add_rename(&mut self, from: ShapeID, to: Identifier) {
+        match self.rename.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => {
+                    let _ = vs.insert(from.into(), NodeValue::String(to.to_string()));
+                }
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Remove the rename entry for the shape with the given identifier, if any.
+    pub fn remove_rename(&mut self, from: &ShapeID) {
+        match self.rename.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => {
+                    let key: Key = from.clone().into();
+                    vs.retain(|k, _| k != &key);
+                }
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Retain only the rename entries for which `predicate` returns `true`.
+    pub fn retain_renames(&mut self, mut predicate: impl FnMut(&ShapeID, &Identifier) -> bool) {
+        match self.rename.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => vs.retain(|k, v| {
+                    let to = match v {
+                        NodeValue::String(s) => Identifier::from_str(s).unwrap(),
+                        _ => invalid_value_variant("String"),
+                    };
+                    predicate(k.as_shape_id().unwrap(), &to)
+                }),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Remove every rename entry declared by this service.
+    pub fn clear_renames(&mut self) {
+        match self.rename.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => vs.clear(),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    ///
+    /// Compute the full transitive closure of shapes reachable from this service: the
+    /// operations and resources bound to it, the service's shared `errors`, and everything
+    /// those in turn reference (operation inputs/outputs/errors, resource identifiers,
+    /// lifecycle operations, and nested resources).
+    ///
+    /// `resolver` looks up the `ShapeKind` for a `ShapeID` so that `Operation` and `Resource`
+    /// shapes can be expanded; this takes a closure rather than a `&Model` so that callers who
+    /// already hold a borrow of the owning model (or a filtered view of it) can still use it.
+    /// Shapes the resolver doesn't know about, or doesn't need to expand (e.g. simple types),
+    /// are still included in the result, just not traversed further.
+    ///
+    /// Returns the de-duplicated set of shape IDs in the closure along with a map from each
+    /// shape to the reason it was included, so callers can build filtered sub-models.
+    ///
+    pub fn closure<F>(&self, resolver: F) -> (HashSet<ShapeID>, HashMap<ShapeID, ClosureReason>)
+    where
+        F: Fn(&ShapeID) -> Option<ShapeKind>,
+    {
+        let mut visited: HashSet<ShapeID> = Default::default();
+        let mut reasons: HashMap<ShapeID, ClosureReason> = Default::default();
+
+        for id in self
+            .operations()
+            .chain(self.resources())
+            .chain(self.errors())
+        {
+            visit_shape(
+                id,
+                ClosureReason::Bound,
+                &resolver,
+                &mut visited,
+                &mut reasons,
+            );
+        }
 
-    array_member! { resources, resource, has_resources, add_resource, append_resources, remove_resource }
+        (visited, reasons)
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -210,7 +387,7 @@ impl Operation {
 
     optional_member! { has_output, output, set_output, unset_output }
 
-    array_member! { errors, error, has_errors, add_error, append_errors, remove_error }
+    array_member! { errors, error, has_errors, add_error, append_errors, remove_error, retain_errors, clear_errors }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -284,7 +461,7 @@ impl Resource {
             Some(v) => match v {
                 NodeValue::Object(vs) => {
                     let key: Key = id.clone().into();
-                    vs.retain(|k, _| k == &key);
+                    vs.retain(|k, _| k != &key);
                 }
                 _ => invalid_value_variant("Object"),
             },
@@ -292,6 +469,30 @@ impl Resource {
         }
     }
 
+    /// Retain only the identifier bindings for which `predicate` returns `true`.
+    pub fn retain_identifiers(&mut self, mut predicate: impl FnMut(&Identifier, &ShapeID) -> bool) {
+        match self.identifiers.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => vs.retain(|k, v| {
+                    predicate(k.as_identifier().unwrap(), v.as_reference().unwrap())
+                }),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
+    /// Remove every identifier binding from this resource.
+    pub fn clear_identifiers(&mut self) {
+        match self.identifiers.value_mut() {
+            Some(v) => match v {
+                NodeValue::Object(vs) => vs.clear(),
+                _ => invalid_value_variant("Object"),
+            },
+            _ => invalid_value_variant("Object"),
+        }
+    }
+
     optional_member! { has_create, create, set_create, unset_create }
 
     optional_member! { has_put, put, set_put, unset_put }
@@ -304,17 +505,200 @@ impl Resource {
 
     optional_member! { has_list, list, set_list, unset_list }
 
-    array_member! { operations, operation, has_operations, add_operation, append_operations, remove_operation }
+    array_member! { operations, operation, has_operations, add_operation, append_operations, remove_operation, retain_operations, clear_operations }
 
-    array_member! { collection_operations, collection_operation, has_collection_operations, add_collection_operation, append_collection_operations, remove_collection_operation }
+    array_member! { collection_operations, collection_operation, has_collection_operations, add_collection_operation, append_collection_operations, remove_collection_operation, retain_collection_operations, clear_collection_operations }
 
-    array_member! { resources, resource, has_resources, add_resource, append_resources, remove_resource }
+    array_member! { resources, resource, has_resources, add_resource, append_resources, remove_resource, retain_resources, clear_resources }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+fn visit_shape<F>(
+    id: &ShapeID,
+    reason: ClosureReason,
+    resolver: &F,
+    visited: &mut HashSet<ShapeID>,
+    reasons: &mut HashMap<ShapeID, ClosureReason>,
+) where
+    F: Fn(&ShapeID) -> Option<ShapeKind>,
+{
+    if visited.contains(id) {
+        // A shape already reached as `Referenced` (e.g. a nested resource) may later turn out
+        // to be bound directly, depending on traversal order; upgrade the recorded reason in
+        // that case instead of leaving it frozen at whatever we saw first. We never need to
+        // recurse again, since the shape's own expansion doesn't depend on why it was reached.
+        if reason == ClosureReason::Bound {
+            reasons.insert(id.clone(), ClosureReason::Bound);
+        }
+        return;
+    }
+    visited.insert(id.clone());
+    reasons.insert(id.clone(), reason);
+
+    match resolver(id) {
+        Some(ShapeKind::Operation(operation)) => {
+            visit_operation(&operation, resolver, visited, reasons)
+        }
+        Some(ShapeKind::Resource(resource)) => {
+            visit_resource(&resource, resolver, visited, reasons)
+        }
+        _ => {}
+    }
+}
+
+fn visit_operation<F>(
+    operation: &Operation,
+    resolver: &F,
+    visited: &mut HashSet<ShapeID>,
+    reasons: &mut HashMap<ShapeID, ClosureReason>,
+) where
+    F: Fn(&ShapeID) -> Option<ShapeKind>,
+{
+    for id in operation.input().into_iter().chain(operation.output()) {
+        visit_shape(id, ClosureReason::Bound, resolver, visited, reasons);
+    }
+    for id in operation.errors() {
+        visit_shape(id, ClosureReason::Bound, resolver, visited, reasons);
+    }
+}
+
+fn visit_resource<F>(
+    resource: &Resource,
+    resolver: &F,
+    visited: &mut HashSet<ShapeID>,
+    reasons: &mut HashMap<ShapeID, ClosureReason>,
+) where
+    F: Fn(&ShapeID) -> Option<ShapeKind>,
+{
+    for (_, id) in resource.identifiers() {
+        visit_shape(id, ClosureReason::Bound, resolver, visited, reasons);
+    }
+    for id in resource
+        .create()
+        .into_iter()
+        .chain(resource.put())
+        .chain(resource.read())
+        .chain(resource.update())
+        .chain(resource.delete())
+        .chain(resource.list())
+    {
+        visit_shape(id, ClosureReason::Bound, resolver, visited, reasons);
+    }
+    for id in resource
+        .operations()
+        .chain(resource.collection_operations())
+    {
+        visit_shape(id, ClosureReason::Bound, resolver, visited, reasons);
+    }
+    for id in resource.resources() {
+        visit_shape(id, ClosureReason::Referenced, resolver, visited, reasons);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as StdHashMap;
+    use std::str::FromStr;
+
+    fn sid(s: &str) -> ShapeID {
+        ShapeID::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn closure_upgrades_reason_to_bound_on_revisit() {
+        let mut service = Service::default();
+        service.add_resource(sid("smithy.example#A"));
+        service.add_resource(sid("smithy.example#B"));
+
+        let mut resource_a = Resource::default();
+        resource_a.add_resource(sid("smithy.example#B"));
+
+        let mut shapes: StdHashMap<ShapeID, ShapeKind> = StdHashMap::new();
+        shapes.insert(sid("smithy.example#A"), ShapeKind::Resource(resource_a));
+        shapes.insert(
+            sid("smithy.example#B"),
+            ShapeKind::Resource(Resource::default()),
+        );
+
+        let (visited, reasons) = service.closure(|id| shapes.get(id).cloned());
+
+        assert!(visited.contains(&sid("smithy.example#B")));
+        assert_eq!(
+            reasons.get(&sid("smithy.example#B")),
+            Some(&ClosureReason::Bound)
+        );
+    }
+
+    #[test]
+    fn closure_terminates_on_resource_cycle() {
+        let mut service = Service::default();
+        service.add_resource(sid("smithy.example#A"));
+
+        let mut resource_a = Resource::default();
+        resource_a.add_resource(sid("smithy.example#B"));
+        let mut resource_b = Resource::default();
+        resource_b.add_resource(sid("smithy.example#A"));
+
+        let mut shapes: StdHashMap<ShapeID, ShapeKind> = StdHashMap::new();
+        shapes.insert(sid("smithy.example#A"), ShapeKind::Resource(resource_a));
+        shapes.insert(sid("smithy.example#B"), ShapeKind::Resource(resource_b));
+
+        let (visited, _reasons) = service.closure(|id| shapes.get(id).cloned());
+
+        assert_eq!(visited.len(), 2);
+    }
+
+    #[test]
+    fn service_errors_round_trip() {
+        let mut service = Service::default();
+        assert!(!service.has_errors());
+
+        service.add_error(sid("smithy.example#WidgetError"));
+
+        assert!(service.has_errors());
+        assert_eq!(
+            service.errors().collect::<Vec<_>>(),
+            vec![&sid("smithy.example#WidgetError")]
+        );
+
+        service.remove_error(&sid("smithy.example#WidgetError"));
+
+        assert!(!service.has_errors());
+    }
+
+    #[test]
+    fn service_rename_round_trip() {
+        let mut service = Service::default();
+        assert!(!service.has_renames());
+
+        service.add_rename(
+            sid("smithy.example#Widget"),
+            Identifier::from_str("RenamedWidget").unwrap(),
+        );
+
+        assert!(service.has_renames());
+        assert_eq!(
+            service.rename().collect::<Vec<_>>(),
+            vec![(
+                &sid("smithy.example#Widget"),
+                Identifier::from_str("RenamedWidget").unwrap()
+            )]
+        );
+
+        service.remove_rename(&sid("smithy.example#Widget"));
+
+        assert!(!service.has_renames());
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------